@@ -6,23 +6,383 @@ use pyo3::wrap_pyfunction;
 use std::borrow::Cow;
 use pyo3::intern;
 
+/// Default spacing between skip-list checkpoints (see `encode_postings_blob`).
+const DEFAULT_SKIP_INTERVAL: u64 = 128;
+
+/// Bit in the posting-blob flag byte: a skip-list checkpoint table follows.
+const FLAG_HAS_SKIPS: u8 = 0x1;
+/// Bit in the posting-blob flag byte: deltas are zigzag-encoded signed
+/// varints rather than raw `i32 as u64` (which wraps to ~10 bytes on a
+/// negative gap). See `zigzag_encode`/`zigzag_decode`.
+const FLAG_ZIGZAG: u8 = 0x2;
+/// Bit in the posting-blob flag byte: the rest of the blob is group-varint
+/// encoded (see `encode_postings_blob_grouped`) rather than the interleaved
+/// protobuf-varint triples `encode_postings_blob` produces. Mutually
+/// exclusive with `FLAG_HAS_SKIPS`/`FLAG_ZIGZAG`, which only describe the
+/// protobuf-varint layout.
+const FLAG_GROUP_CODEC: u8 = 0x4;
+
+/// Number of bytes needed to hold `v` as a little-endian integer (at least 1,
+/// even for zero, since group-varint always reserves a slot for every value).
+#[inline]
+fn group_varint_byte_len(v: u32) -> usize {
+    if v == 0 {
+        1
+    } else {
+        ((32 - v.leading_zeros()) as usize).div_ceil(8)
+    }
+}
+
+/// The four per-slot byte lengths and their sum for every possible
+/// group-varint control byte, computed once at compile time so decoding
+/// never has to branch on bit patterns.
+const GROUP_VARINT_LUT: [(usize, usize, usize, usize, usize); 256] = {
+    let mut table = [(0usize, 0usize, 0usize, 0usize, 0usize); 256];
+    let mut control = 0usize;
+    while control < 256 {
+        let l0 = (control & 0x3) + 1;
+        let l1 = ((control >> 2) & 0x3) + 1;
+        let l2 = ((control >> 4) & 0x3) + 1;
+        let l3 = ((control >> 6) & 0x3) + 1;
+        table[control] = (l0, l1, l2, l3, l0 + l1 + l2 + l3);
+        control += 1;
+    }
+    table
+};
+
+/// Group-varint encode a stream of `u32`s: every 4 values share one control
+/// byte whose four 2-bit fields each give that value's byte length (1-4),
+/// followed by the packed little-endian bytes for all four. The last group
+/// may hold fewer than 4 values; unused control bits are left as zero.
+fn group_varint_encode_stream(values: &[u32]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(values.len() + values.len() / 4 + 1);
+    for chunk in values.chunks(4) {
+        let mut control = 0u8;
+        let mut packed = Vec::with_capacity(16);
+        for (j, &v) in chunk.iter().enumerate() {
+            let len = group_varint_byte_len(v);
+            control |= ((len - 1) as u8) << (j * 2);
+            packed.extend_from_slice(&v.to_le_bytes()[..len]);
+        }
+        out.push(control);
+        out.extend_from_slice(&packed);
+    }
+    out
+}
+
+/// Decode `count` `u32`s previously encoded by `group_varint_encode_stream`,
+/// advancing `pos` past the consumed bytes.
+fn group_varint_decode_stream(data: &[u8], pos: &mut usize, count: usize) -> PyResult<Vec<u32>> {
+    // Every value takes at least one packed byte, so a truthful `count`
+    // can never exceed the bytes left to read. Reject it up front instead
+    // of trusting a corrupted/absurd count straight into `with_capacity`.
+    if count > data.len().saturating_sub(*pos) {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "group-varint: value count exceeds remaining data length",
+        ));
+    }
+
+    let mut values = Vec::with_capacity(count);
+    let mut remaining = count;
+
+    while remaining > 0 {
+        let control = *data.get(*pos).ok_or_else(|| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "group-varint: truncated control byte",
+            )
+        })?;
+        *pos += 1;
+
+        let (l0, l1, l2, l3, _total) = GROUP_VARINT_LUT[control as usize];
+        let in_group = remaining.min(4);
+        for &len in [l0, l1, l2, l3].iter().take(in_group) {
+            if *pos + len > data.len() {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    "group-varint: truncated value bytes",
+                ));
+            }
+            let mut buf = [0u8; 4];
+            buf[..len].copy_from_slice(&data[*pos..*pos + len]);
+            *pos += len;
+            values.push(u32::from_le_bytes(buf));
+        }
+        remaining -= in_group;
+    }
+
+    Ok(values)
+}
+
+/// Encode postings into the three parallel streams (delta, content_freq,
+/// title_freq) that `decode_postings_blob` expects when it sees
+/// `FLAG_GROUP_CODEC`, each group-varint packed separately so that runs of
+/// same-length values pack tightly. Deltas are always zigzagged, since
+/// group-varint only holds unsigned values. Does not support a skip-list
+/// checkpoint table.
+fn encode_postings_blob_grouped(postings: &[(i32, i32, i32)]) -> PyResult<Vec<u8>> {
+    let mut prev_doc_id = 0i32;
+    let mut deltas = Vec::with_capacity(postings.len());
+    let mut content_freqs = Vec::with_capacity(postings.len());
+    let mut title_freqs = Vec::with_capacity(postings.len());
+
+    for &(doc_id, content_freq, title_freq) in postings {
+        let delta = (doc_id - prev_doc_id) as i64;
+        prev_doc_id = doc_id;
+        let zigzagged = zigzag_encode(delta);
+        if zigzagged > u32::MAX as u64 {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "doc_id gap too large for codec=\"group\" (group-varint values are \
+                 u32); use codec=\"varint\" for postings with very large doc_id gaps",
+            ));
+        }
+        deltas.push(zigzagged as u32);
+        content_freqs.push(content_freq as u32);
+        title_freqs.push(title_freq as u32);
+    }
+
+    let mut result = Vec::new();
+    result.push(FLAG_GROUP_CODEC);
+    encode_varint_to_vec(&mut result, postings.len() as u64);
+    result.extend(group_varint_encode_stream(&deltas));
+    result.extend(group_varint_encode_stream(&content_freqs));
+    result.extend(group_varint_encode_stream(&title_freqs));
+    Ok(result)
+}
+
+/// One skip-list checkpoint: the absolute doc_id it starts at, its byte
+/// offset into the posting bytes, and the max content_freq/title_freq seen
+/// anywhere in its block (i.e. up to the next checkpoint). The block-max
+/// pair lets `query_topk` bound a block's contribution to its scoring
+/// function (see `query_topk`'s doc comment) without decoding it.
+type SkipEntry = (i32, u64, i32, i32);
+
+/// Encode already-ordered postings into a posting-bytes blob, optionally
+/// prefixed with a skip-list checkpoint table.
+///
+/// Output layout: a 1-byte flag (`FLAG_HAS_SKIPS`, `FLAG_ZIGZAG`), then if
+/// `FLAG_HAS_SKIPS` is set, a varint checkpoint count followed by that many
+/// `(absolute_doc_id, byte_offset, max_content_freq, max_title_freq)`
+/// varint quadruples (see `SkipEntry`) giving sublinear entry points into
+/// the posting bytes that follow, then the posting bytes themselves
+/// (delta/content_freq/title_freq varint triples). A checkpoint is recorded
+/// every `skip_interval` postings. When `zigzag` is set, the
+/// delta field is zigzag-encoded so negative gaps (e.g. from frequency
+/// ordering) stay compact instead of wrapping to a near-u64::MAX varint.
+fn encode_postings_blob(
+    postings: &[(i32, i32, i32)],
+    with_skips: bool,
+    skip_interval: u64,
+    zigzag: bool,
+) -> PyResult<Vec<u8>> {
+    if with_skips && skip_interval == 0 {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "skip_interval must be greater than zero",
+        ));
+    }
+    if with_skips && !postings.windows(2).all(|w| w[0].0 <= w[1].0) {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "with_skips requires postings sorted ascending by doc_id: the skip \
+             table's checkpoints are binary-searched assuming monotonic doc_ids",
+        ));
+    }
+
+    let mut posting_bytes = Vec::with_capacity(postings.len() * 15);
+    let mut skip_table: Vec<SkipEntry> = Vec::new();
+    let mut prev_doc_id = 0i32;
+
+    for (i, &(doc_id, content_freq, title_freq)) in postings.iter().enumerate() {
+        if with_skips && (i as u64).is_multiple_of(skip_interval) {
+            skip_table.push((doc_id, posting_bytes.len() as u64, 0, 0));
+        }
+        if let Some(block) = skip_table.last_mut() {
+            block.2 = block.2.max(content_freq);
+            block.3 = block.3.max(title_freq);
+        }
+
+        let delta = (doc_id - prev_doc_id) as i64;
+        prev_doc_id = doc_id;
+        let encoded_delta = if zigzag { zigzag_encode(delta) } else { delta as u64 };
+        encode_varint_to_vec(&mut posting_bytes, encoded_delta);
+        encode_varint_to_vec(&mut posting_bytes, content_freq as u64);
+        encode_varint_to_vec(&mut posting_bytes, title_freq as u64);
+    }
+
+    let mut flag = 0u8;
+    if with_skips && !skip_table.is_empty() {
+        flag |= FLAG_HAS_SKIPS;
+    }
+    if zigzag {
+        flag |= FLAG_ZIGZAG;
+    }
+
+    let mut result = Vec::with_capacity(posting_bytes.len() + 16);
+    result.push(flag);
+    if flag & FLAG_HAS_SKIPS != 0 {
+        encode_varint_to_vec(&mut result, skip_table.len() as u64);
+        for (doc_id, byte_offset, max_content_freq, max_title_freq) in &skip_table {
+            encode_varint_to_vec(&mut result, *doc_id as u64);
+            encode_varint_to_vec(&mut result, *byte_offset);
+            encode_varint_to_vec(&mut result, *max_content_freq as u64);
+            encode_varint_to_vec(&mut result, *max_title_freq as u64);
+        }
+    }
+    result.extend_from_slice(&posting_bytes);
+    Ok(result)
+}
+
+/// Parse the flag byte (and skip table, if present) prefixed onto a posting
+/// bytes blob by `encode_postings_blob`. Returns the checkpoint table
+/// (empty if none), whether deltas are zigzag-encoded, and the offset into
+/// `data` where the posting bytes proper begin.
+fn decode_postings_header(data: &[u8]) -> PyResult<(Vec<SkipEntry>, bool, usize)> {
+    if data.is_empty() {
+        return Ok((Vec::new(), false, 0));
+    }
+
+    let flag = data[0];
+    let mut pos = 1;
+    let zigzag = flag & FLAG_ZIGZAG != 0;
+    if flag & FLAG_HAS_SKIPS == 0 {
+        return Ok((Vec::new(), zigzag, pos));
+    }
+
+    let (count, consumed) = decode_varint(&data[pos..])?;
+    pos += consumed;
+
+    let mut skip_table = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+        let (doc_id, consumed) = decode_varint(&data[pos..])?;
+        pos += consumed;
+        let (byte_offset, consumed) = decode_varint(&data[pos..])?;
+        pos += consumed;
+        let (max_content_freq, consumed) = decode_varint(&data[pos..])?;
+        pos += consumed;
+        let (max_title_freq, consumed) = decode_varint(&data[pos..])?;
+        pos += consumed;
+        skip_table.push((doc_id as i32, byte_offset, max_content_freq as i32, max_title_freq as i32));
+    }
+
+    Ok((skip_table, zigzag, pos))
+}
+
+/// Decode a delta varint according to the blob's zigzag flag.
+#[inline]
+fn decode_delta(raw: u64, zigzag: bool) -> i32 {
+    if zigzag {
+        zigzag_decode(raw) as i32
+    } else {
+        raw as i32
+    }
+}
+
+/// Fully decode a posting-bytes blob into `(doc_id, content_freq,
+/// title_freq)` tuples, auto-selecting the protobuf-varint or group-varint
+/// path from the blob's flag byte (`FLAG_GROUP_CODEC`). Shared by
+/// `decode_posting_list` and `merge_posting_lists`.
+///
+/// `legacy` decodes `data` as the pre-flag-byte format this crate produced
+/// before skip lists, zigzag and the group codec existed: bare
+/// delta/content_freq/title_freq varint triples with no prefix byte at all.
+/// There is no marker distinguishing that format from the current one (its
+/// first byte is an arbitrary delta varint, not a reserved flag value), so
+/// callers holding bytes written by that older encoder must say so
+/// explicitly rather than have them silently misparsed as the new format.
+fn decode_postings_blob(data: &[u8], legacy: bool) -> PyResult<Vec<(i32, i32, i32)>> {
+    if legacy {
+        let mut postings = Vec::new();
+        let mut pos = 0;
+        let mut prev_doc_id = 0i32;
+
+        while pos < data.len() {
+            let (delta, consumed) = decode_varint(&data[pos..])?;
+            pos += consumed;
+            let (content_freq, consumed) = decode_varint(&data[pos..])?;
+            pos += consumed;
+            let (title_freq, consumed) = decode_varint(&data[pos..])?;
+            pos += consumed;
+
+            prev_doc_id += delta as i32;
+            postings.push((prev_doc_id, content_freq as i32, title_freq as i32));
+        }
+
+        return Ok(postings);
+    }
+
+    if !data.is_empty() && data[0] & FLAG_GROUP_CODEC != 0 {
+        let mut pos = 1;
+        let (num_postings, consumed) = decode_varint(&data[pos..])?;
+        pos += consumed;
+        let num_postings = num_postings as usize;
+
+        let deltas = group_varint_decode_stream(data, &mut pos, num_postings)?;
+        let content_freqs = group_varint_decode_stream(data, &mut pos, num_postings)?;
+        let title_freqs = group_varint_decode_stream(data, &mut pos, num_postings)?;
+
+        let mut postings = Vec::with_capacity(num_postings);
+        let mut prev_doc_id = 0i32;
+        for i in 0..num_postings {
+            prev_doc_id += zigzag_decode(deltas[i] as u64) as i32;
+            postings.push((prev_doc_id, content_freqs[i] as i32, title_freqs[i] as i32));
+        }
+        return Ok(postings);
+    }
+
+    let (_skip_table, zigzag, mut pos) = decode_postings_header(data)?;
+    let mut postings = Vec::new();
+    let mut prev_doc_id = 0i32;
+
+    while pos < data.len() {
+        let (delta, consumed) = decode_varint(&data[pos..])?;
+        pos += consumed;
+        let (content_freq, consumed) = decode_varint(&data[pos..])?;
+        pos += consumed;
+        let (title_freq, consumed) = decode_varint(&data[pos..])?;
+        pos += consumed;
+
+        prev_doc_id += decode_delta(delta, zigzag);
+        postings.push((prev_doc_id, content_freq as i32, title_freq as i32));
+    }
+
+    Ok(postings)
+}
+
 /// Encode a posting list using delta encoding and varint compression.
 ///
-/// Postings are sorted by document ID and delta-encoded:
-/// - First doc_id is stored as-is
-/// - Subsequent doc_ids are stored as deltas
+/// Deltas are zigzag-encoded, so both orderings below stay compact:
+/// - `order="docid"`: postings are sorted ascending by doc_id, so deltas
+///   are always non-negative (zigzag is then a no-op cost).
+/// - `order="freq"` (default): postings stay sorted by descending
+///   frequency, so doc_id deltas are frequently negative; zigzag keeps
+///   those gaps small instead of wrapping to a ~10-byte varint.
 ///
 /// Args:
 ///     postings: List of (doc_id, content_freq, title_freq) tuples.
-///     assume_sorted: If True, skip sorting (postings already sorted by doc_id).
+///     assume_sorted: If True, skip sorting (postings already in the
+///         order implied by `order`).
+///     order: "docid" to sort ascending by doc_id, "freq" to sort by
+///         descending content_freq + title_freq (ties broken by
+///         content_freq, then title_freq, both descending, then doc_id
+///         ascending).
+///     with_skips: If True, prepend a skip-list checkpoint table so a
+///         `PostingCursor` can `seek()` into the result sublinearly.
+///         Requires `order="docid"`, since the skip table is binary-searched
+///         assuming ascending doc_ids. Not supported with `codec="group"`.
+///     skip_interval: Number of postings between skip-list checkpoints.
+///     codec: "varint" (default) for the interleaved protobuf-varint
+///         triples, or "group" for group-varint-packed parallel streams,
+///         which decodes faster on large posting lists at the cost of
+///         `PostingCursor` support.
 ///
 /// Returns:
 ///     Compressed bytes representation of the posting list.
 #[pyfunction]
-#[pyo3(signature = (postings, assume_sorted=false))]
+#[pyo3(signature = (postings, assume_sorted=false, order="freq", with_skips=false, skip_interval=DEFAULT_SKIP_INTERVAL, codec="varint"))]
 fn encode_posting_list(
     postings: Bound<'_, PyList>,
     assume_sorted: bool,
+    order: &str,
+    with_skips: bool,
+    skip_interval: u64,
+    codec: &str,
 ) -> PyResult<Vec<u8>> {
     let len = postings.len();
     if len == 0 {
@@ -46,26 +406,38 @@ fn encode_posting_list(
 
     // Sort if needed
     if !assume_sorted {
-        postings_vec.sort_unstable_by_key(|x| (-x.1 - x.2, -x.1, -x.2, x.0));
+        match order {
+            "docid" => postings_vec.sort_unstable_by_key(|x| x.0),
+            "freq" => postings_vec.sort_unstable_by_key(|x| (-x.1 - x.2, -x.1, -x.2, x.0)),
+            _ => {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    "order must be \"docid\" or \"freq\"",
+                ))
+            }
+        }
     }
 
-    // Pre-allocate buffer with estimated size
-    let estimated_size = len * 15; // More conservative estimate
-    let mut result = Vec::with_capacity(estimated_size);
-    let mut prev_doc_id = 0i32;
-
-    for (doc_id, content_freq, title_freq) in postings_vec {
-        // Delta encode document ID
-        let delta = doc_id - prev_doc_id;
-        prev_doc_id = doc_id;
-
-        // Encode varints using Protocol Buffers format (same as Python)
-        encode_varint_to_vec(&mut result, delta as u64);
-        encode_varint_to_vec(&mut result, content_freq as u64);
-        encode_varint_to_vec(&mut result, title_freq as u64);
+    if with_skips && order != "docid" {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "with_skips requires order=\"docid\": PostingCursor.seek() binary-searches \
+             the skip table assuming doc_ids are ascending",
+        ));
     }
 
-    Ok(result)
+    match codec {
+        "varint" => encode_postings_blob(&postings_vec, with_skips, skip_interval, true),
+        "group" => {
+            if with_skips {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    "with_skips is not supported with codec=\"group\"",
+                ));
+            }
+            encode_postings_blob_grouped(&postings_vec)
+        }
+        _ => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "codec must be \"varint\" or \"group\"",
+        )),
+    }
 }
 
 /// Encode varint using Protocol Buffers format (same as Python implementation).
@@ -78,6 +450,19 @@ fn encode_varint_to_vec(result: &mut Vec<u8>, mut n: u64) {
     result.push((n & 0x7F) as u8);
 }
 
+/// Zigzag-encode a signed delta so small negatives map to small positives,
+/// keeping the varint short regardless of sign (protobuf's `sint` scheme).
+#[inline]
+fn zigzag_encode(n: i64) -> u64 {
+    ((n << 1) ^ (n >> 63)) as u64
+}
+
+/// Inverse of `zigzag_encode`.
+#[inline]
+fn zigzag_decode(z: u64) -> i64 {
+    ((z >> 1) as i64) ^ -((z & 1) as i64)
+}
+
 /// Encode an integer using variable-length encoding (varint).
 #[pyfunction]
 fn encode_varint(n: i64) -> PyResult<Vec<u8>> {
@@ -119,6 +504,30 @@ fn decode_varint(data: &[u8]) -> PyResult<(u64, usize)> {
     ))
 }
 
+/// Read one varint directly off a byte stream, one byte at a time. Used by
+/// the binary-block readers instead of guessing a fixed-size buffer: a
+/// posting_list_len or freq varint can legitimately take more than a single
+/// byte, and a buffer sized for the common case silently truncates the read
+/// for anything larger.
+#[inline]
+fn read_varint_from_reader(reader: &mut impl Read) -> PyResult<u64> {
+    let mut result = 0u64;
+    let mut shift = 0;
+
+    for _ in 0..10 {
+        let mut byte = [0u8; 1];
+        reader.read_exact(&mut byte)?;
+        result |= ((byte[0] & 0x7F) as u64) << shift;
+        if (byte[0] & 0x80) == 0 {
+            return Ok(result);
+        }
+        shift += 7;
+    }
+
+    Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+        "Invalid varint encoding: too many bytes",
+    ))
+}
 
 #[pyfunction]
 fn read_varint<'py>(py: Python<'py>, f: Bound<'py, PyAny>) -> PyResult<Option<u64>> {
@@ -173,37 +582,236 @@ fn read_varint<'py>(py: Python<'py>, f: Bound<'py, PyAny>) -> PyResult<Option<u6
 
 /// Decode a posting list from compressed bytes.
 ///
+/// Auto-selects the protobuf-varint or group-varint decode path based on
+/// the blob's flag byte, so callers don't need to know which `codec` the
+/// data was encoded with.
+///
 /// Args:
-///     data: Compressed bytes representation of the posting list.
+///     data: Compressed bytes representation of the posting list, as
+///         produced by `encode_posting_list` (with or without skips).
+///     legacy: If True, treat `data` as the pre-flag-byte format produced
+///         by this crate's original `encode_posting_list` (bare
+///         delta/content_freq/title_freq varint triples, no skip table, no
+///         zigzag). Set this for bytes persisted before skip lists, zigzag
+///         deltas or the group codec were introduced; there is no way to
+///         detect that format automatically.
 ///
 /// Returns:
 ///     List of (doc_id, content_freq, title_freq) tuples.
 #[pyfunction]
-fn decode_posting_list<'py>(py: Python<'py>, data: &[u8]) -> PyResult<Bound<'py, PyList>> {
-    let mut postings = Vec::new();
-    let mut pos = 0;
-    let mut prev_doc_id = 0i32;
+#[pyo3(signature = (data, legacy=false))]
+fn decode_posting_list<'py>(py: Python<'py>, data: &[u8], legacy: bool) -> PyResult<Bound<'py, PyList>> {
+    Ok(PyList::new(py, decode_postings_blob(data, legacy)?)?)
+}
 
-    while pos < data.len() {
-        // Decode delta
-        let (delta, consumed) = decode_varint(&data[pos..])?;
-        pos += consumed;
+/// Result of `PostingCursor.seek()`, mirroring the shape of tantivy's
+/// `DocSet` skip result: whether the target doc_id was hit exactly,
+/// stepped over (no posting for it), or the cursor ran out of postings.
+#[pyclass(eq, eq_int)]
+#[derive(Clone, Copy, PartialEq)]
+enum SeekStatus {
+    Reached,
+    OverStep,
+    End,
+}
 
-        // Decode content_freq
-        let (content_freq, consumed) = decode_varint(&data[pos..])?;
-        pos += consumed;
+/// Random-access cursor over a compressed posting list.
+///
+/// Unlike `decode_posting_list`, which eagerly decodes every delta,
+/// `PostingCursor` decodes lazily via `advance()` and can jump ahead via
+/// `seek()`. If the blob was encoded with `with_skips=True`, `seek` uses the
+/// embedded checkpoint table to binary-search to the nearest checkpoint at
+/// or before the target and scans forward from there instead of from the
+/// start, making it sublinear in the common case.
+#[pyclass]
+struct PostingCursor {
+    posting_bytes: Vec<u8>,
+    skip_table: Vec<SkipEntry>,
+    zigzag: bool,
+    pos: usize,
+    doc_id: i32,
+    content_freq: i32,
+    title_freq: i32,
+    started: bool,
+    exhausted: bool,
+}
 
-        // Decode title_freq
-        let (title_freq, consumed) = decode_varint(&data[pos..])?;
-        pos += consumed;
+#[pymethods]
+impl PostingCursor {
+    #[new]
+    fn new(data: &[u8]) -> PyResult<Self> {
+        if !data.is_empty() && data[0] & FLAG_GROUP_CODEC != 0 {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "PostingCursor does not support codec=\"group\" blobs; use decode_posting_list instead",
+            ));
+        }
+        let (skip_table, zigzag, offset) = decode_postings_header(data)?;
+        Ok(PostingCursor {
+            posting_bytes: data[offset..].to_vec(),
+            skip_table,
+            zigzag,
+            pos: 0,
+            doc_id: 0,
+            content_freq: 0,
+            title_freq: 0,
+            started: false,
+            exhausted: false,
+        })
+    }
 
-        // Reconstruct doc_id from delta
-        prev_doc_id += delta as i32;
+    /// Decode the next posting, returning its doc_id, or `None` at the end.
+    fn advance(&mut self) -> PyResult<Option<i32>> {
+        if self.exhausted || self.pos >= self.posting_bytes.len() {
+            self.exhausted = true;
+            return Ok(None);
+        }
 
-        postings.push((prev_doc_id, content_freq as i32, title_freq as i32));
+        let (delta, consumed) = decode_varint(&self.posting_bytes[self.pos..])?;
+        self.pos += consumed;
+        let (content_freq, consumed) = decode_varint(&self.posting_bytes[self.pos..])?;
+        self.pos += consumed;
+        let (title_freq, consumed) = decode_varint(&self.posting_bytes[self.pos..])?;
+        self.pos += consumed;
+
+        self.doc_id += decode_delta(delta, self.zigzag);
+        self.content_freq = content_freq as i32;
+        self.title_freq = title_freq as i32;
+        self.started = true;
+
+        Ok(Some(self.doc_id))
+    }
+
+    /// Advance until the current doc_id is >= target, using the skip table
+    /// (if any) to jump ahead before scanning. Returns (status, doc_id).
+    fn seek(&mut self, target: i32) -> PyResult<(SeekStatus, Option<i32>)> {
+        if self.started && !self.exhausted && self.doc_id >= target {
+            let status = if self.doc_id == target {
+                SeekStatus::Reached
+            } else {
+                SeekStatus::OverStep
+            };
+            return Ok((status, Some(self.doc_id)));
+        }
+
+        if let Some(&(checkpoint_doc_id, byte_offset, ..)) = self.best_checkpoint(target) {
+            if byte_offset as usize >= self.pos {
+                // The checkpoint's doc_id is that of the posting stored at
+                // byte_offset; back out its delta, which advance() re-adds.
+                let at_checkpoint = self.posting_bytes.get(byte_offset as usize..).unwrap_or(&[]);
+                let (delta, _) = decode_varint(at_checkpoint)?;
+                self.pos = byte_offset as usize;
+                self.doc_id = checkpoint_doc_id - decode_delta(delta, self.zigzag);
+                self.started = true;
+                self.exhausted = false;
+            }
+        }
+
+        loop {
+            match self.advance()? {
+                Some(doc_id) if doc_id == target => return Ok((SeekStatus::Reached, Some(doc_id))),
+                Some(doc_id) if doc_id > target => return Ok((SeekStatus::OverStep, Some(doc_id))),
+                Some(_) => continue,
+                None => return Ok((SeekStatus::End, None)),
+            }
+        }
+    }
+
+    fn doc_id(&self) -> Option<i32> {
+        if self.started {
+            Some(self.doc_id)
+        } else {
+            None
+        }
+    }
+
+    fn content_freq(&self) -> i32 {
+        self.content_freq
+    }
+
+    fn title_freq(&self) -> i32 {
+        self.title_freq
+    }
+}
+
+impl PostingCursor {
+    /// Largest skip-table checkpoint whose doc_id is <= target, if any.
+    fn best_checkpoint(&self, target: i32) -> Option<&SkipEntry> {
+        match self.skip_table.binary_search_by(|(doc_id, ..)| doc_id.cmp(&target)) {
+            Ok(i) => Some(&self.skip_table[i]),
+            Err(0) => None,
+            Err(i) => Some(&self.skip_table[i - 1]),
+        }
+    }
+
+    /// The checkpoint block the cursor is currently positioned in, i.e. the
+    /// last checkpoint at or before the cursor's current doc_id.
+    fn current_block(&self) -> Option<&SkipEntry> {
+        if self.skip_table.is_empty() {
+            return None;
+        }
+        self.best_checkpoint(self.doc_id).or_else(|| self.skip_table.first())
+    }
+
+    /// The doc_id at which the current block ends (exclusive), or `None` if
+    /// this is the last block.
+    fn current_block_end(&self) -> Option<i32> {
+        let current = self.current_block()?;
+        self.skip_table
+            .iter()
+            .find(|entry| entry.0 > current.0)
+            .map(|entry| entry.0)
+    }
+}
+
+/// One term's header fields and raw (still-encoded) posting bytes, as laid
+/// out by `write_binary_block`. Shared by every reader that walks a binary
+/// block file, so the term/varint/posting-list framing is parsed in exactly
+/// one place.
+struct TermRecord {
+    term: String,
+    doc_freq_content: u64,
+    doc_freq_title: u64,
+    max_content_freq: u64,
+    max_title_freq: u64,
+    posting_bytes: Vec<u8>,
+}
+
+/// Read one term record from `reader`, positioned at a term boundary
+/// (immediately after the file's `num_terms` header, or wherever a prior
+/// call left off). Returns `None` at a clean EOF (no more terms to read).
+fn read_term_record(reader: &mut impl Read) -> PyResult<Option<TermRecord>> {
+    let mut term_len_bytes = [0u8; 4];
+    if reader.read_exact(&mut term_len_bytes).is_err() {
+        return Ok(None);
     }
+    let term_len = u32::from_le_bytes(term_len_bytes) as usize;
 
-    Ok(PyList::new(py, postings)?)
+    let mut term_bytes = vec![0u8; term_len];
+    reader.read_exact(&mut term_bytes)?;
+    let term = String::from_utf8(term_bytes).map_err(|e| {
+        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Invalid UTF-8: {}", e))
+    })?;
+
+    // Five header varints, read straight off the stream since none of them
+    // has a fixed width: doc_freq_content, doc_freq_title, max_content_freq,
+    // max_title_freq, posting_list_len.
+    let doc_freq_content = read_varint_from_reader(reader)?;
+    let doc_freq_title = read_varint_from_reader(reader)?;
+    let max_content_freq = read_varint_from_reader(reader)?;
+    let max_title_freq = read_varint_from_reader(reader)?;
+    let posting_list_len = read_varint_from_reader(reader)?;
+
+    let mut posting_bytes = vec![0u8; posting_list_len as usize];
+    reader.read_exact(&mut posting_bytes)?;
+
+    Ok(Some(TermRecord {
+        term,
+        doc_freq_content,
+        doc_freq_title,
+        max_content_freq,
+        max_title_freq,
+        posting_bytes,
+    }))
 }
 
 /// Read a term from a binary block file.
@@ -213,8 +821,8 @@ fn decode_posting_list<'py>(py: Python<'py>, data: &[u8]) -> PyResult<Bound<'py,
 ///     offset: Byte offset where the term starts.
 ///
 /// Returns:
-///     Tuple of (term, doc_freq_content, doc_freq_title, postings, next_offset)
-///     or None if end of file.
+///     Tuple of (term, doc_freq_content, doc_freq_title, postings,
+///     max_content_freq, max_title_freq, next_offset) or None if end of file.
 #[pyfunction]
 fn read_term_at_offset<'py>(
     py: Python<'py>,
@@ -227,46 +835,24 @@ fn read_term_at_offset<'py>(
     use std::io::Seek;
     file.seek(std::io::SeekFrom::Start(offset))?;
 
-    // Read term length (4 bytes)
-    let mut term_len_bytes = [0u8; 4];
-    if file.read_exact(&mut term_len_bytes).is_err() {
-        return Ok(None);
-    }
-    let term_len = u32::from_le_bytes(term_len_bytes) as usize;
-
-    // Read term bytes
-    let mut term_bytes = vec![0u8; term_len];
-    file.read_exact(&mut term_bytes)?;
-    let term = String::from_utf8(term_bytes).map_err(|e| {
-        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Invalid UTF-8: {}", e))
-    })?;
-
-    // Read varints into buffer for decoding
-    let mut varint_buffer = vec![0u8; 128]; // Should be enough for 3 varints
-    file.read_exact(&mut varint_buffer[..10])?; // Read at least enough for first varint
-
-    let (doc_freq_content, consumed1) = decode_varint(&varint_buffer)?;
-    let (doc_freq_title, consumed2) = decode_varint(&varint_buffer[consumed1..])?;
-    let (posting_list_len, consumed3) = decode_varint(&varint_buffer[consumed1 + consumed2..])?;
-
-    // Calculate actual bytes consumed for varints
-    let total_varint_bytes = consumed1 + consumed2 + consumed3;
-
-    // Read posting list data
-    let mut posting_list_data = vec![0u8; posting_list_len as usize];
-    file.read_exact(&mut posting_list_data)?;
+    let record = match read_term_record(&mut file)? {
+        Some(record) => record,
+        None => return Ok(None),
+    };
 
     // Decode postings
-    let postings = decode_posting_list(py, &posting_list_data)?;
+    let postings = decode_posting_list(py, &record.posting_bytes, false)?;
 
-    // Calculate next offset
-    let next_offset = offset + 4 + term_len as u64 + total_varint_bytes as u64 + posting_list_len;
+    // The next term starts wherever the stream is positioned now.
+    let next_offset = file.stream_position()?;
 
     let result_items = vec![
-        term.into_pyobject(py)?.into_any(),
-        doc_freq_content.into_pyobject(py)?.into_any(),
-        doc_freq_title.into_pyobject(py)?.into_any(),
+        record.term.into_pyobject(py)?.into_any(),
+        record.doc_freq_content.into_pyobject(py)?.into_any(),
+        record.doc_freq_title.into_pyobject(py)?.into_any(),
         postings.into_pyobject(py)?.into_any(),
+        record.max_content_freq.into_pyobject(py)?.into_any(),
+        record.max_title_freq.into_pyobject(py)?.into_any(),
         next_offset.into_pyobject(py)?.into_any(),
     ];
     let result = PyTuple::new(py, &result_items)?;
@@ -280,7 +866,8 @@ fn read_term_at_offset<'py>(
 ///     file_path: Path to the binary block file.
 ///
 /// Returns:
-///     Iterator of (term, doc_freq_content, doc_freq_title, postings) tuples.
+///     Iterator of (term, doc_freq_content, doc_freq_title, postings,
+///     max_content_freq, max_title_freq) tuples.
 #[pyfunction]
 fn iter_block_terms<'py>(py: Python<'py>, file_path: &str) -> PyResult<Bound<'py, PyList>> {
     let mut file = BufReader::new(File::open(file_path)?);
@@ -293,39 +880,20 @@ fn iter_block_terms<'py>(py: Python<'py>, file_path: &str) -> PyResult<Bound<'py
     let mut results = Vec::with_capacity(num_terms as usize);
 
     for _ in 0..num_terms {
-        // Read term length
-        let mut term_len_bytes = [0u8; 4];
-        if file.read_exact(&mut term_len_bytes).is_err() {
-            break;
-        }
-        let term_len = u32::from_le_bytes(term_len_bytes) as usize;
-
-        // Read term
-        let mut term_bytes = vec![0u8; term_len];
-        file.read_exact(&mut term_bytes)?;
-        let term = String::from_utf8(term_bytes).map_err(|e| {
-            PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Invalid UTF-8: {}", e))
-        })?;
-
-        // Read varints (doc_freq_content, doc_freq_title, posting_list_len)
-        let mut varint_buffer = vec![0u8; 64];
-        file.read_exact(&mut varint_buffer[..20])?;
-
-        let (doc_freq_content, consumed1) = decode_varint(&varint_buffer)?;
-        let (doc_freq_title, consumed2) = decode_varint(&varint_buffer[consumed1..])?;
-        let (posting_list_len, _) = decode_varint(&varint_buffer[consumed1 + consumed2..])?;
-
-        // Read posting list
-        let mut posting_list_data = vec![0u8; posting_list_len as usize];
-        file.read_exact(&mut posting_list_data)?;
+        let record = match read_term_record(&mut file)? {
+            Some(record) => record,
+            None => break,
+        };
 
-        let postings = decode_posting_list(py, &posting_list_data)?;
+        let postings = decode_posting_list(py, &record.posting_bytes, false)?;
 
         let tuple_items = vec![
-            term.into_pyobject(py)?.into_any(),
-            doc_freq_content.into_pyobject(py)?.into_any(),
-            doc_freq_title.into_pyobject(py)?.into_any(),
+            record.term.into_pyobject(py)?.into_any(),
+            record.doc_freq_content.into_pyobject(py)?.into_any(),
+            record.doc_freq_title.into_pyobject(py)?.into_any(),
             postings.into_pyobject(py)?.into_any(),
+            record.max_content_freq.into_pyobject(py)?.into_any(),
+            record.max_title_freq.into_pyobject(py)?.into_any(),
         ];
         let result = PyTuple::new(py, &tuple_items)?;
 
@@ -362,48 +930,15 @@ fn merge_posting_lists(
     for item in postings_bytes_list.iter() {
         // Extract bytes from Python bytes object
         let posting_bytes: &[u8] = item.extract()?;
-        
-        let mut pos = 0;
-        let mut prev_doc_id = 0i32;
-
-        while pos < posting_bytes.len() {
-            // Decode delta
-            let (delta, consumed) = decode_varint(&posting_bytes[pos..])?;
-            pos += consumed;
-
-            // Decode content_freq
-            let (content_freq, consumed) = decode_varint(&posting_bytes[pos..])?;
-            pos += consumed;
-
-            // Decode title_freq
-            let (title_freq, consumed) = decode_varint(&posting_bytes[pos..])?;
-            pos += consumed;
-
-            // Reconstruct doc_id from delta
-            prev_doc_id += delta as i32;
-            all_postings.push((prev_doc_id, content_freq as i32, title_freq as i32));
-        }
+        all_postings.extend(decode_postings_blob(posting_bytes, false)?);
     }
 
     // Sort the merged postings
     all_postings.sort_unstable_by_key(|x| (-x.1 - 4 * x.2, -x.1, -x.2, x.0));
 
-    // Encode back to compressed format
-    let mut result = Vec::with_capacity(all_postings.len() * 15);
-    let mut prev_doc_id = 0i32;
-
-    for (doc_id, content_freq, title_freq) in all_postings {
-        // Delta encode document ID
-        let delta = doc_id - prev_doc_id;
-        prev_doc_id = doc_id;
-
-        // Encode varints
-        encode_varint_to_vec(&mut result, delta as u64);
-        encode_varint_to_vec(&mut result, content_freq as u64);
-        encode_varint_to_vec(&mut result, title_freq as u64);
-    }
-
-    Ok(result)
+    // Encode back to compressed format (zigzagged: this ordering frequently
+    // produces negative doc_id deltas)
+    encode_postings_blob(&all_postings, false, DEFAULT_SKIP_INTERVAL, true)
 }
 
 
@@ -414,18 +949,39 @@ fn merge_posting_lists(
 ///     doc_freqs: List of (doc_freq_content, doc_freq_title) tuples.
 ///     postings: List of posting lists (each is a list of (doc_id, content_freq, title_freq)).
 ///     output_path: Path to output binary block file.
+///     with_skips: If True, give each term's posting blob a skip-list
+///         checkpoint table so `PostingCursor.seek()` can jump into it.
+///         Requires each posting list already sorted ascending by doc_id
+///         (raises if not). Not supported with `codec="group"`.
+///     skip_interval: Number of postings between skip-list checkpoints.
+///     codec: "varint" (default) or "group" (group-varint, faster bulk
+///         decode, no skip-list support). See `encode_posting_list`.
 #[pyfunction]
+#[pyo3(signature = (terms, doc_freqs, postings, output_path, with_skips=false, skip_interval=DEFAULT_SKIP_INTERVAL, codec="varint"))]
 fn write_binary_block(
     terms: Vec<String>,
     doc_freqs: Vec<(u64, u64)>,
     postings: Vec<Vec<(i32, i32, i32)>>,
     output_path: &str,
+    with_skips: bool,
+    skip_interval: u64,
+    codec: &str,
 ) -> PyResult<()> {
     if terms.len() != doc_freqs.len() || terms.len() != postings.len() {
         return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
             "terms, doc_freqs, and postings must have the same length",
         ));
     }
+    if codec != "varint" && codec != "group" {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "codec must be \"varint\" or \"group\"",
+        ));
+    }
+    if codec == "group" && with_skips {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "with_skips is not supported with codec=\"group\"",
+        ));
+    }
 
     let mut file = BufWriter::new(File::create(output_path)?);
 
@@ -448,16 +1004,23 @@ fn write_binary_block(
         encode_varint_to_vec(&mut varint_buf, doc_freq_content);
         encode_varint_to_vec(&mut varint_buf, doc_freq_title);
 
-        // Encode posting list
-        let mut encoded_postings = Vec::new();
-        let mut prev_doc_id = 0i32;
-        for &(doc_id, content_freq, title_freq) in posting_list {
-            let delta = doc_id - prev_doc_id;
-            prev_doc_id = doc_id;
-            encode_varint_to_vec(&mut encoded_postings, delta as u64);
-            encode_varint_to_vec(&mut encoded_postings, content_freq as u64);
-            encode_varint_to_vec(&mut encoded_postings, title_freq as u64);
-        }
+        // Write this term's max content_freq/title_freq, so `query_topk` can
+        // bound the term's contribution to its score (see `query_topk`'s doc
+        // comment) without decoding a single posting.
+        let max_content_freq = posting_list.iter().map(|p| p.1).max().unwrap_or(0);
+        let max_title_freq = posting_list.iter().map(|p| p.2).max().unwrap_or(0);
+        encode_varint_to_vec(&mut varint_buf, max_content_freq as u64);
+        encode_varint_to_vec(&mut varint_buf, max_title_freq as u64);
+
+        // Encode posting list (flag byte + optional skip table + postings).
+        // Deltas are always zigzagged in the varint path: it's a no-op cost
+        // for docid-ordered callers and keeps freq-ordered callers' negative
+        // gaps compact.
+        let encoded_postings = if codec == "group" {
+            encode_postings_blob_grouped(posting_list)?
+        } else {
+            encode_postings_blob(posting_list, with_skips, skip_interval, true)?
+        };
 
         // Write posting list length
         encode_varint_to_vec(&mut varint_buf, encoded_postings.len() as u64);
@@ -490,13 +1053,586 @@ fn get_block_stats(file_path: &str) -> PyResult<(u64, u64)> {
     Ok((num_terms, file_size))
 }
 
+// --- CIFF (Common Index File Format) interop -------------------------------
+//
+// CIFF represents an index as a stream of length-delimited protobuf
+// messages: one Header, then one PostingsList per term, then one DocRecord
+// per document. Each message is prefixed by a plain protobuf varint giving
+// its encoded byte length, so framing reuses `encode_varint_to_vec` /
+// `decode_varint` exactly like the rest of this crate. We don't depend on a
+// protobuf crate; the handful of field kinds CIFF needs (varint, 64-bit,
+// length-delimited) are encoded/decoded by hand below.
+
+const CIFF_VERSION: u64 = 1;
+
+#[inline]
+fn ciff_write_varint_field(buf: &mut Vec<u8>, field_num: u32, value: u64) {
+    let tag = (field_num as u64) << 3;
+    encode_varint_to_vec(buf, tag);
+    encode_varint_to_vec(buf, value);
+}
+
+#[inline]
+fn ciff_write_string_field(buf: &mut Vec<u8>, field_num: u32, s: &str) {
+    let tag = ((field_num as u64) << 3) | 2;
+    encode_varint_to_vec(buf, tag);
+    encode_varint_to_vec(buf, s.len() as u64);
+    buf.extend_from_slice(s.as_bytes());
+}
+
+#[inline]
+fn ciff_write_bytes_field(buf: &mut Vec<u8>, field_num: u32, bytes: &[u8]) {
+    let tag = ((field_num as u64) << 3) | 2;
+    encode_varint_to_vec(buf, tag);
+    encode_varint_to_vec(buf, bytes.len() as u64);
+    buf.extend_from_slice(bytes);
+}
+
+#[inline]
+fn ciff_write_double_field(buf: &mut Vec<u8>, field_num: u32, value: f64) {
+    let tag = ((field_num as u64) << 3) | 1;
+    encode_varint_to_vec(buf, tag);
+    buf.extend_from_slice(&value.to_le_bytes());
+}
+
+/// Write a length-delimited protobuf message: varint byte length, then body.
+fn ciff_write_message(file: &mut impl Write, message: &[u8]) -> PyResult<()> {
+    let mut len_buf = Vec::new();
+    encode_varint_to_vec(&mut len_buf, message.len() as u64);
+    file.write_all(&len_buf)?;
+    file.write_all(message)?;
+    Ok(())
+}
+
+/// One decoded protobuf field: (field_num, wire_type, varint_value, raw_bytes).
+/// `varint_value` is only meaningful for wire type 0; `raw_bytes` holds the
+/// payload for 64-bit (wire type 1) and length-delimited (wire type 2) fields.
+type CiffField = (u32, u8, u64, Vec<u8>);
+
+/// Parse a flat (non-nested) protobuf message into its raw fields. CIFF only
+/// uses varint, 64-bit and length-delimited fields, so that's all this
+/// supports.
+fn ciff_parse_fields(data: &[u8]) -> PyResult<Vec<CiffField>> {
+    let mut pos = 0;
+    let mut fields = Vec::new();
+
+    while pos < data.len() {
+        let (tag, consumed) = decode_varint(&data[pos..])?;
+        pos += consumed;
+        let field_num = (tag >> 3) as u32;
+        let wire_type = (tag & 0x7) as u8;
+
+        match wire_type {
+            0 => {
+                let (value, consumed) = decode_varint(&data[pos..])?;
+                pos += consumed;
+                fields.push((field_num, wire_type, value, Vec::new()));
+            }
+            1 => {
+                if pos + 8 > data.len() {
+                    return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                        "CIFF: truncated message, expected 8 more bytes for a 64-bit field",
+                    ));
+                }
+                let bytes = data[pos..pos + 8].to_vec();
+                pos += 8;
+                fields.push((field_num, wire_type, 0, bytes));
+            }
+            2 => {
+                let (len, consumed) = decode_varint(&data[pos..])?;
+                pos += consumed;
+                let len = len as usize;
+                if pos + len > data.len() {
+                    return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                        "CIFF: truncated message, expected more bytes for a length-delimited field",
+                    ));
+                }
+                let bytes = data[pos..pos + len].to_vec();
+                pos += len;
+                fields.push((field_num, wire_type, 0, bytes));
+            }
+            _ => {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    "CIFF: unsupported protobuf wire type",
+                ))
+            }
+        }
+    }
+
+    Ok(fields)
+}
+
+/// Read one length-prefixed protobuf message from `file`, or `None` at EOF.
+fn ciff_read_message(file: &mut impl Read) -> PyResult<Option<Vec<u8>>> {
+    let mut first_byte = [0u8; 1];
+    if file.read(&mut first_byte)? == 0 {
+        return Ok(None);
+    }
+
+    // Decode the remainder of the length varint a byte at a time, since we
+    // already consumed the first byte above to detect EOF.
+    let mut len_bytes = vec![first_byte[0]];
+    while len_bytes.last().map(|b| b & 0x80 != 0).unwrap_or(false) {
+        let mut byte = [0u8; 1];
+        file.read_exact(&mut byte)?;
+        len_bytes.push(byte[0]);
+    }
+    let (message_len, _) = decode_varint(&len_bytes)?;
+
+    let mut message = vec![0u8; message_len as usize];
+    file.read_exact(&mut message)?;
+    Ok(Some(message))
+}
+
+/// Read a CIFF file, yielding one `(term, df, postings)` tuple per term, with
+/// postings in the same `(doc_id, content_freq, title_freq)` form that
+/// `iter_block_terms` returns, alongside the collection header and doc
+/// records.
+///
+/// CIFF's single `tf` per posting is mapped to this crate's `content_freq`,
+/// with `title_freq` always 0 (CIFF has no notion of title frequency).
+///
+/// Args:
+///     file_path: Path to the CIFF file.
+///
+/// Returns:
+///     Tuple of (header, terms, doc_records):
+///       header: (version, num_postings_lists, num_docs, total_postings_lists,
+///                total_terms_in_collection, average_doclength, description)
+///       terms: list of (term, df, postings) where postings is a list of
+///              (doc_id, content_freq, title_freq)
+///       doc_records: list of (docid, collection_docid, doclength)
+#[pyfunction]
+fn read_ciff<'py>(py: Python<'py>, file_path: &str) -> PyResult<Bound<'py, PyTuple>> {
+    let mut file = BufReader::new(File::open(file_path)?);
+
+    let header_bytes = ciff_read_message(&mut file)?.ok_or_else(|| {
+        PyErr::new::<pyo3::exceptions::PyValueError, _>("CIFF: missing header message")
+    })?;
+
+    let mut version = 0u64;
+    let mut num_postings_lists = 0u64;
+    let mut num_docs = 0u64;
+    let mut total_postings_lists = 0u64;
+    let mut total_terms_in_collection = 0u64;
+    let mut average_doclength = 0f64;
+    let mut description = String::new();
+
+    for (field_num, wire_type, value, bytes) in ciff_parse_fields(&header_bytes)? {
+        match field_num {
+            1 => version = value,
+            2 => num_postings_lists = value,
+            3 => num_docs = value,
+            4 => total_postings_lists = value,
+            6 => total_terms_in_collection = value,
+            7 if wire_type == 1 => {
+                average_doclength = f64::from_le_bytes(bytes.try_into().unwrap())
+            }
+            8 => {
+                description = String::from_utf8(bytes).map_err(|e| {
+                    PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                        "CIFF: invalid UTF-8 in description: {}",
+                        e
+                    ))
+                })?
+            }
+            _ => {}
+        }
+    }
+
+    let header = PyTuple::new(
+        py,
+        &[
+            version.into_pyobject(py)?.into_any(),
+            num_postings_lists.into_pyobject(py)?.into_any(),
+            num_docs.into_pyobject(py)?.into_any(),
+            total_postings_lists.into_pyobject(py)?.into_any(),
+            total_terms_in_collection.into_pyobject(py)?.into_any(),
+            average_doclength.into_pyobject(py)?.into_any(),
+            description.into_pyobject(py)?.into_any(),
+        ],
+    )?;
+
+    let mut terms = Vec::with_capacity(num_postings_lists as usize);
+    for _ in 0..num_postings_lists {
+        let message = ciff_read_message(&mut file)?.ok_or_else(|| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "CIFF: truncated file, expected PostingsList",
+            )
+        })?;
+
+        let mut term = String::new();
+        let mut df = 0u64;
+        let mut postings: Vec<(i32, i32, i32)> = Vec::new();
+        let mut prev_doc_id = 0i32;
+
+        for (field_num, _wire_type, value, bytes) in ciff_parse_fields(&message)? {
+            match field_num {
+                1 => {
+                    term = String::from_utf8(bytes).map_err(|e| {
+                        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                            "CIFF: invalid UTF-8 in term: {}",
+                            e
+                        ))
+                    })?
+                }
+                2 => df = value,
+                3 => {} // cf: not surfaced in the (term, df, postings) shape
+                4 => {
+                    let mut posting_docid = 0i64;
+                    let mut posting_tf = 0i64;
+                    for (pf_num, _pf_wire, pf_value, _pf_bytes) in ciff_parse_fields(&bytes)? {
+                        match pf_num {
+                            1 => posting_docid = pf_value as i64,
+                            2 => posting_tf = pf_value as i64,
+                            _ => {}
+                        }
+                    }
+                    prev_doc_id += posting_docid as i32;
+                    postings.push((prev_doc_id, posting_tf as i32, 0));
+                }
+                _ => {}
+            }
+        }
+
+        let tuple = PyTuple::new(
+            py,
+            &[
+                term.into_pyobject(py)?.into_any(),
+                df.into_pyobject(py)?.into_any(),
+                PyList::new(py, postings)?.into_any(),
+            ],
+        )?;
+        terms.push(tuple);
+    }
+
+    let mut doc_records = Vec::with_capacity(num_docs as usize);
+    for _ in 0..num_docs {
+        let message = ciff_read_message(&mut file)?.ok_or_else(|| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "CIFF: truncated file, expected DocRecord",
+            )
+        })?;
+
+        let mut docid = 0i64;
+        let mut collection_docid = String::new();
+        let mut doclength = 0i64;
+
+        for (field_num, _wire_type, value, bytes) in ciff_parse_fields(&message)? {
+            match field_num {
+                1 => docid = value as i64,
+                2 => {
+                    collection_docid = String::from_utf8(bytes).map_err(|e| {
+                        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                            "CIFF: invalid UTF-8 in collection_docid: {}",
+                            e
+                        ))
+                    })?
+                }
+                3 => doclength = value as i64,
+                _ => {}
+            }
+        }
+
+        doc_records.push(PyTuple::new(
+            py,
+            &[
+                docid.into_pyobject(py)?.into_any(),
+                collection_docid.into_pyobject(py)?.into_any(),
+                doclength.into_pyobject(py)?.into_any(),
+            ],
+        )?);
+    }
+
+    PyTuple::new(
+        py,
+        &[
+            header.into_any(),
+            PyList::new(py, terms)?.into_any(),
+            PyList::new(py, doc_records)?.into_any(),
+        ],
+    )
+}
+
+/// Write a CIFF file from this crate's native term/posting representation.
+///
+/// Each posting's `content_freq` and `title_freq` are summed into CIFF's
+/// single `tf`, and postings are written in ascending doc_id order with
+/// gap-encoded docids, matching CIFF's on-disk convention.
+///
+/// Args:
+///     terms: List of terms (sorted).
+///     ciff_freqs: List of (df, cf) tuples, one per term.
+///     postings: List of posting lists (each is a list of (doc_id, content_freq, title_freq)).
+///     doc_records: List of (docid, collection_docid, doclength) tuples.
+///     total_postings_lists: Total postings lists across the whole collection (for partial dumps).
+///     total_terms_in_collection: Sum of collection term frequencies.
+///     average_doclength: Average document length across the collection.
+///     description: Free-form description of the collection/export.
+///     output_path: Path to write the CIFF file to.
+#[pyfunction]
+#[pyo3(signature = (terms, ciff_freqs, postings, doc_records, total_postings_lists, total_terms_in_collection, average_doclength, description, output_path))]
+#[allow(clippy::too_many_arguments)] // mirrors the Python-facing signature; not worth a builder for one pyfunction
+fn write_ciff(
+    terms: Vec<String>,
+    ciff_freqs: Vec<(u64, u64)>,
+    postings: Vec<Vec<(i32, i32, i32)>>,
+    doc_records: Vec<(i32, String, i32)>,
+    total_postings_lists: u64,
+    total_terms_in_collection: u64,
+    average_doclength: f64,
+    description: &str,
+    output_path: &str,
+) -> PyResult<()> {
+    if terms.len() != ciff_freqs.len() || terms.len() != postings.len() {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "terms, ciff_freqs, and postings must have the same length",
+        ));
+    }
+
+    let mut file = BufWriter::new(File::create(output_path)?);
+
+    let mut header = Vec::new();
+    ciff_write_varint_field(&mut header, 1, CIFF_VERSION);
+    ciff_write_varint_field(&mut header, 2, terms.len() as u64);
+    ciff_write_varint_field(&mut header, 3, doc_records.len() as u64);
+    ciff_write_varint_field(&mut header, 4, total_postings_lists);
+    ciff_write_varint_field(&mut header, 6, total_terms_in_collection);
+    ciff_write_double_field(&mut header, 7, average_doclength);
+    ciff_write_string_field(&mut header, 8, description);
+    ciff_write_message(&mut file, &header)?;
+
+    for i in 0..terms.len() {
+        let (df, cf) = ciff_freqs[i];
+        let mut sorted_postings = postings[i].clone();
+        sorted_postings.sort_unstable_by_key(|x| x.0);
+
+        let mut message = Vec::new();
+        ciff_write_string_field(&mut message, 1, &terms[i]);
+        ciff_write_varint_field(&mut message, 2, df);
+        ciff_write_varint_field(&mut message, 3, cf);
+
+        let mut prev_doc_id = 0i32;
+        for (doc_id, content_freq, title_freq) in sorted_postings {
+            let gap = (doc_id - prev_doc_id) as u64;
+            prev_doc_id = doc_id;
+            let tf = (content_freq + title_freq) as u64;
+
+            let mut posting = Vec::new();
+            ciff_write_varint_field(&mut posting, 1, gap);
+            ciff_write_varint_field(&mut posting, 2, tf);
+            ciff_write_bytes_field(&mut message, 4, &posting);
+        }
+
+        ciff_write_message(&mut file, &message)?;
+    }
+
+    for (docid, collection_docid, doclength) in &doc_records {
+        let mut message = Vec::new();
+        ciff_write_varint_field(&mut message, 1, *docid as u64);
+        ciff_write_string_field(&mut message, 2, collection_docid);
+        ciff_write_varint_field(&mut message, 3, *doclength as u64);
+        ciff_write_message(&mut file, &message)?;
+    }
+
+    file.flush()?;
+    Ok(())
+}
+
+// --- Block-max top-k scoring ------------------------------------------------
+//
+// `query_topk` is a disjunctive (OR) top-k scorer over terms already written
+// by `write_binary_block`: it opens one `PostingCursor` per requested term
+// and merges them like a WAND/Block-Max-WAND query, using the term-level
+// max_content_freq/max_title_freq (and, when the posting lists were written
+// with `with_skips=True`, each cursor's current skip-block max) as an upper
+// bound on the score a term can still contribute. Whenever the summed upper
+// bound of every active cursor can't beat the current k-th best score, every
+// cursor is seeked to the earliest end of the active cursors' current blocks
+// instead of being decoded posting-by-posting.
+
+/// One requested term's cursor state while `query_topk` merges postings.
+struct TopkCursor {
+    cursor: PostingCursor,
+    doc_id: Option<i32>,
+    term_max_content_freq: i32,
+    term_max_title_freq: i32,
+}
+
+/// Highest score a cursor's current position could still contribute: the
+/// current skip-block's max, if the posting list has a skip table, else the
+/// term's overall max (the only bound available without one).
+fn cursor_upper_bound(c: &TopkCursor, weights: (f64, f64)) -> f64 {
+    match c.cursor.current_block() {
+        Some(&(_, _, block_max_content_freq, block_max_title_freq)) => {
+            weights.0 * block_max_content_freq as f64 + weights.1 * block_max_title_freq as f64
+        }
+        None => weights.0 * c.term_max_content_freq as f64 + weights.1 * c.term_max_title_freq as f64,
+    }
+}
+
+/// The score a result must beat to enter the top-k, i.e. the current k-th
+/// best score, or negative infinity while the heap isn't full yet.
+fn topk_threshold(heap: &[(i32, f64)], k: usize) -> f64 {
+    if heap.len() < k {
+        f64::NEG_INFINITY
+    } else {
+        heap.iter().map(|&(_, score)| score).fold(f64::INFINITY, f64::min)
+    }
+}
+
+/// Insert `(doc_id, score)` into a bounded top-k result set, evicting the
+/// current worst entry once the set has reached capacity.
+fn topk_push(heap: &mut Vec<(i32, f64)>, k: usize, doc_id: i32, score: f64) {
+    if heap.len() < k {
+        heap.push((doc_id, score));
+        return;
+    }
+    if let Some((worst_idx, _)) = heap
+        .iter()
+        .enumerate()
+        .min_by(|a, b| a.1 .1.partial_cmp(&b.1 .1).unwrap())
+    {
+        if score > heap[worst_idx].1 {
+            heap[worst_idx] = (doc_id, score);
+        }
+    }
+}
+
+/// Scan a binary block file and collect the raw posting bytes and term-level
+/// max_content_freq/max_title_freq for every term in `wanted`, in file order.
+fn collect_term_postings(
+    file_path: &str,
+    wanted: &std::collections::HashSet<&str>,
+) -> PyResult<Vec<(Vec<u8>, i32, i32)>> {
+    let mut file = BufReader::new(File::open(file_path)?);
+
+    let mut num_terms_bytes = [0u8; 8];
+    file.read_exact(&mut num_terms_bytes)?;
+    let num_terms = u64::from_le_bytes(num_terms_bytes);
+
+    let mut found = Vec::new();
+
+    for _ in 0..num_terms {
+        let record = match read_term_record(&mut file)? {
+            Some(record) => record,
+            None => break,
+        };
+
+        if wanted.contains(record.term.as_str()) {
+            found.push((
+                record.posting_bytes,
+                record.max_content_freq as i32,
+                record.max_title_freq as i32,
+            ));
+        }
+    }
+
+    Ok(found)
+}
+
+/// Top-k disjunctive query over posting lists stored in a binary block file.
+///
+/// Opens a `PostingCursor` per requested term and merges them as an OR
+/// query, scoring each doc_id as `weights.0 * content_freq + weights.1 *
+/// title_freq` (summed across terms that match that doc_id) — simple
+/// linear term weighting for now, leaving room for BM25 later. Uses each
+/// term's block-max metadata to skip whole skip-list blocks that can't
+/// possibly beat the current k-th best score.
+///
+/// Args:
+///     file_path: Path to the binary block file.
+///     terms: Terms to query for.
+///     k: Number of top results to return.
+///     weights: (content_freq_weight, title_freq_weight).
+///
+/// Returns:
+///     Up to `k` (doc_id, score) pairs, sorted by score descending.
+#[pyfunction]
+fn query_topk(
+    file_path: &str,
+    terms: Vec<String>,
+    k: usize,
+    weights: (f64, f64),
+) -> PyResult<Vec<(i32, f64)>> {
+    if k == 0 || terms.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let wanted: std::collections::HashSet<&str> = terms.iter().map(|t| t.as_str()).collect();
+    let found = collect_term_postings(file_path, &wanted)?;
+
+    let mut cursors = Vec::with_capacity(found.len());
+    for (posting_bytes, term_max_content_freq, term_max_title_freq) in found {
+        let mut cursor = PostingCursor::new(&posting_bytes)?;
+        let doc_id = cursor.advance()?;
+        cursors.push(TopkCursor {
+            cursor,
+            doc_id,
+            term_max_content_freq,
+            term_max_title_freq,
+        });
+    }
+
+    let mut heap: Vec<(i32, f64)> = Vec::with_capacity(k);
+
+    while let Some(min_doc) = cursors.iter().filter_map(|c| c.doc_id).min() {
+        let threshold = topk_threshold(&heap, k);
+        let upper_bound: f64 = cursors
+            .iter()
+            .filter(|c| c.doc_id.is_some())
+            .map(|c| cursor_upper_bound(c, weights))
+            .sum();
+
+        if heap.len() >= k && upper_bound <= threshold {
+            // Every active cursor's bound holds up to the earliest end of
+            // their current blocks, so no doc_id before it can unseat the
+            // k-th best score: seek every active cursor to that point rather
+            // than decoding those postings one by one.
+            let skip_to = cursors
+                .iter()
+                .filter(|c| c.doc_id.is_some())
+                .filter_map(|c| c.cursor.current_block_end())
+                .min();
+            for c in cursors.iter_mut() {
+                if c.doc_id.is_none() {
+                    continue;
+                }
+                if let Some(block_end) = skip_to {
+                    let (_, doc_id) = c.cursor.seek(block_end)?;
+                    c.doc_id = doc_id;
+                } else {
+                    // No block end to jump to; fall back to a single
+                    // decode step so the loop still makes progress.
+                    c.doc_id = c.cursor.advance()?;
+                }
+            }
+            continue;
+        }
+
+        // Merge step: sum every active cursor currently sitting on min_doc.
+        let mut score = 0.0;
+        for c in cursors.iter_mut() {
+            if c.doc_id == Some(min_doc) {
+                score += weights.0 * c.cursor.content_freq() as f64
+                    + weights.1 * c.cursor.title_freq() as f64;
+                c.doc_id = c.cursor.advance()?;
+            }
+        }
+        topk_push(&mut heap, k, min_doc, score);
+    }
+
+    heap.sort_unstable_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+    Ok(heap)
+}
+
 #[pymodule]
 fn py_rust_encode_varint(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add("__version__", "0.3.7")?;
     m.add("__author__", "André Ribeiro & Rúben Garrido")?;
     m.add("__email__", "andrepedoribeiro04@gmail.com & rubentavaresgarrido@gmail.com")?;
     m.add("__package__", "py_rust_encode_varint")?;
-    m.add("__all_functions__", ["encode_posting_list", "encode_varint", "decode_posting_list", "read_term_at_offset", "iter_block_terms", "write_binary_block", "get_block_stats", "merge_posting_lists", "read_varint"])?;
+    m.add("__all_functions__", ["encode_posting_list", "encode_varint", "decode_posting_list", "read_term_at_offset", "iter_block_terms", "write_binary_block", "get_block_stats", "merge_posting_lists", "read_varint", "read_ciff", "write_ciff", "query_topk"])?;
 
     m.add_function(wrap_pyfunction!(encode_posting_list, m)?)?;
     m.add_function(wrap_pyfunction!(encode_varint, m)?)?;
@@ -507,5 +1643,10 @@ fn py_rust_encode_varint(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(get_block_stats, m)?)?;
     m.add_function(wrap_pyfunction!(merge_posting_lists, m)?)?;
     m.add_function(wrap_pyfunction!(read_varint, m)?)?;
+    m.add_function(wrap_pyfunction!(read_ciff, m)?)?;
+    m.add_function(wrap_pyfunction!(write_ciff, m)?)?;
+    m.add_function(wrap_pyfunction!(query_topk, m)?)?;
+    m.add_class::<PostingCursor>()?;
+    m.add_class::<SeekStatus>()?;
     Ok(())
 }